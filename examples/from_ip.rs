@@ -1,13 +1,15 @@
-use elgato_keylight::KeyLight;
+use elgato_keylight::{Easing, KeyLight, KeyLightConfig};
 use std::error::Error;
 use std::net::Ipv4Addr;
 use std::str::FromStr;
+use std::time::Duration;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     //Lookup lamp using IP
     let ip = Ipv4Addr::from_str("10.0.1.32")?;
-    let mut kl = KeyLight::new_from_ip("Key Light 2000", ip, true).await?;
+    let mut kl =
+        KeyLight::new_from_ip("Key Light 2000", ip, true, KeyLightConfig::default()).await?;
 
     //Turn on the light
     kl.set_power(true).await?;
@@ -15,14 +17,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
     //Set brightness to 30
     kl.set_brightness(30).await?;
 
-    //Slowly increase the color temperature
-    for n in 143..344 {
-        //Set temperature
-        kl.set_temperature(n).await?;
-
-        //Sleep for 1 ms
-        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
-    }
+    //Smoothly fade the color temperature up to 7000K
+    kl.fade_temperature(7000, Duration::from_secs(2), Easing::EaseInOutCubic)
+        .await?;
 
     //Turn of the light
     kl.set_power(false).await?;
@@ -0,0 +1,168 @@
+//! Persistent cache of previously discovered lights.
+//!
+//! mDNS discovery in [`KeyLight::new_from_name`](crate::keylight::KeyLight::new_from_name) can
+//! take seconds and fails outright when a light is briefly unreachable. This cache lets it
+//! connect directly by the last-known IP first, only falling back to a fresh scan if that
+//! address no longer responds.
+//!
+//! Setting up more than one light concurrently is the common case (see
+//! [`KeyLight::discover_all`](crate::keylight::KeyLight::discover_all) and
+//! [`LightGroup`](crate::group::LightGroup)), so all reads and writes are routed through a
+//! single process-wide, mutex-guarded instance rather than independent load-modify-write
+//! cycles per call, which would otherwise race and silently drop concurrently discovered
+//! lights.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use tokio::sync::{Mutex, OnceCell};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedLight {
+    pub addr: Ipv4Addr,
+    pub port: u16,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct DeviceCache {
+    lights: HashMap<String, CachedLight>,
+}
+
+static SHARED: OnceCell<Mutex<DeviceCache>> = OnceCell::const_new();
+
+impl DeviceCache {
+    fn path() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("elgato-keylight-rs")
+            .join("devices.json")
+    }
+
+    /// The single shared instance backing every call into this module, loaded from disk on
+    /// first access.
+    async fn shared() -> &'static Mutex<DeviceCache> {
+        SHARED
+            .get_or_init(|| async {
+                let cache = match tokio::fs::read_to_string(Self::path()).await {
+                    Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+                    Err(_) => DeviceCache::default(),
+                };
+                Mutex::new(cache)
+            })
+            .await
+    }
+
+    pub async fn get(name: &str) -> Option<CachedLight> {
+        Self::shared().await.lock().await.lights.get(name).cloned()
+    }
+
+    /// Record `name`'s address/port and persist the cache to disk. Errors saving are ignored:
+    /// the cache is a latency optimization, not a source of truth. The shared lock is held for
+    /// the full insert-then-write so concurrent `upsert` calls can't clobber each other.
+    pub async fn upsert(name: &str, addr: Ipv4Addr, port: u16) {
+        let mut cache = Self::shared().await.lock().await;
+        cache.insert_and_persist(name, addr, port, &Self::path()).await;
+    }
+
+    /// Insert `name`'s address/port into `self` and write the whole cache to `path`, creating
+    /// its parent directory if needed. Split out of `upsert` so the persistence logic can be
+    /// exercised against a throwaway path in tests without going through the process-wide
+    /// `SHARED` instance.
+    async fn insert_and_persist(&mut self, name: &str, addr: Ipv4Addr, port: u16, path: &PathBuf) {
+        self.lights
+            .insert(name.to_string(), CachedLight { addr, port });
+
+        if let Some(dir) = path.parent() {
+            let _ = tokio::fs::create_dir_all(dir).await;
+        }
+
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            let _ = tokio::fs::write(path, data).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut cache = DeviceCache::default();
+        cache.lights.insert(
+            "Key Light".to_string(),
+            CachedLight {
+                addr: Ipv4Addr::new(192, 168, 1, 50),
+                port: 9123,
+            },
+        );
+
+        let json = serde_json::to_string_pretty(&cache).unwrap();
+        let restored: DeviceCache = serde_json::from_str(&json).unwrap();
+
+        let light = restored.lights.get("Key Light").unwrap();
+        assert_eq!(light.addr, Ipv4Addr::new(192, 168, 1, 50));
+        assert_eq!(light.port, 9123);
+    }
+
+    #[test]
+    fn unknown_name_is_absent() {
+        let cache = DeviceCache::default();
+        assert!(cache.lights.get("nonexistent").is_none());
+    }
+
+    /// Exercises the real `insert_and_persist` logic behind `upsert` against a throwaway temp
+    /// dir, so it doesn't touch the caller's machine or the process-wide `SHARED` instance.
+    #[tokio::test]
+    async fn insert_and_persist_then_reload_round_trips() {
+        let dir = std::env::temp_dir().join(format!(
+            "elgato-keylight-rs-test-{}-{}",
+            std::process::id(),
+            "insert_and_persist_then_reload_round_trips"
+        ));
+        let path = dir.join("devices.json");
+
+        let mut cache = DeviceCache::default();
+        cache
+            .insert_and_persist("Key Light Air", Ipv4Addr::new(10, 0, 0, 7), 9123, &path)
+            .await;
+
+        let data = tokio::fs::read_to_string(&path).await.unwrap();
+        let restored: DeviceCache = serde_json::from_str(&data).unwrap();
+        assert_eq!(
+            restored.lights.get("Key Light Air").unwrap().addr,
+            Ipv4Addr::new(10, 0, 0, 7)
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    /// A second `insert_and_persist` call must preserve entries from the first instead of
+    /// clobbering the whole file, since `upsert` persists the entire in-memory cache each time.
+    #[tokio::test]
+    async fn insert_and_persist_keeps_prior_entries() {
+        let dir = std::env::temp_dir().join(format!(
+            "elgato-keylight-rs-test-{}-{}",
+            std::process::id(),
+            "insert_and_persist_keeps_prior_entries"
+        ));
+        let path = dir.join("devices.json");
+
+        let mut cache = DeviceCache::default();
+        cache
+            .insert_and_persist("Key Light", Ipv4Addr::new(192, 168, 1, 50), 9123, &path)
+            .await;
+        cache
+            .insert_and_persist("Key Light Air", Ipv4Addr::new(192, 168, 1, 51), 9123, &path)
+            .await;
+
+        let data = tokio::fs::read_to_string(&path).await.unwrap();
+        let restored: DeviceCache = serde_json::from_str(&data).unwrap();
+        assert_eq!(restored.lights.len(), 2);
+        assert!(restored.lights.contains_key("Key Light"));
+        assert!(restored.lights.contains_key("Key Light Air"));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+//! Synchronized control of multiple lights as a single unit.
+
+use crate::keylight::{ElgatoError, KeyLight};
+use futures::future::join_all;
+
+/// A set of lights controlled together, e.g. a key/fill/back setup.
+///
+/// Each setter fans its call out to every light concurrently. Lights can carry a weight
+/// (default 1.0) so, for example, a back light can be driven at half the brightness of the
+/// key light for the same `set_brightness`/`set_relative_brightness` call.
+pub struct LightGroup {
+    lights: Vec<KeyLight>,
+    weights: Vec<f64>,
+}
+
+impl LightGroup {
+    /// Create a group where every light is weighted equally (1.0).
+    pub fn new(lights: Vec<KeyLight>) -> Self {
+        let weights = vec![1.0; lights.len()];
+        LightGroup { lights, weights }
+    }
+
+    /// Apply a per-light weight, used by `set_brightness` and `set_relative_brightness`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` isn't the same length as the group's lights.
+    pub fn with_weights(mut self, weights: Vec<f64>) -> Self {
+        assert_eq!(
+            weights.len(),
+            self.lights.len(),
+            "one weight is required per light in the group"
+        );
+        self.weights = weights;
+        self
+    }
+
+    /// Turn every light in the group on/off.
+    pub async fn set_power(&mut self, power: bool) -> Vec<Result<(), ElgatoError>> {
+        join_all(self.lights.iter_mut().map(|light| light.set_power(power))).await
+    }
+
+    /// Set every light's brightness, scaled by its weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - Value between 0-100, before weighting
+    pub async fn set_brightness(&mut self, brightness: u8) -> Vec<Result<(), ElgatoError>> {
+        join_all(
+            self.lights
+                .iter_mut()
+                .zip(self.weights.iter())
+                .map(|(light, weight)| {
+                    light.set_brightness(weighted_brightness(brightness, *weight))
+                }),
+        )
+        .await
+    }
+
+    /// Adjust every light's brightness relative to its current value, scaled by its weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - f64 between -1.0 and 1.0, before weighting
+    pub async fn set_relative_brightness(
+        &mut self,
+        brightness: f64,
+    ) -> Vec<Result<f64, ElgatoError>> {
+        join_all(
+            self.lights
+                .iter_mut()
+                .zip(self.weights.iter())
+                .map(|(light, weight)| light.set_relative_brightness(brightness * weight)),
+        )
+        .await
+    }
+
+    /// Set every light's color temperature.
+    ///
+    /// # Arguments
+    ///
+    /// * `temperature` - Value between 2900 - 7000 (Kelvin)
+    pub async fn set_temperature(&mut self, temperature: u32) -> Vec<Result<(), ElgatoError>> {
+        join_all(
+            self.lights
+                .iter_mut()
+                .map(|light| light.set_temperature(temperature)),
+        )
+        .await
+    }
+}
+
+/// Scale `brightness` (0-100) by `weight` and clamp back into the valid 0-100 range.
+fn weighted_brightness(brightness: u8, weight: f64) -> u8 {
+    (brightness as f64 * weight).clamp(0.0, 100.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_brightness_scales_down() {
+        assert_eq!(weighted_brightness(100, 0.5), 50);
+    }
+
+    #[test]
+    fn weighted_brightness_clamps_to_upper_bound() {
+        assert_eq!(weighted_brightness(100, 2.0), 100);
+        assert_eq!(weighted_brightness(80, 1.5), 100);
+    }
+
+    #[test]
+    fn weighted_brightness_clamps_to_lower_bound() {
+        assert_eq!(weighted_brightness(50, -1.0), 0);
+        assert_eq!(weighted_brightness(0, 1.0), 0);
+    }
+
+    #[test]
+    fn with_weights_accepts_matching_length() {
+        let group = LightGroup::new(Vec::new()).with_weights(Vec::new());
+        assert!(group.weights.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "one weight is required per light in the group")]
+    fn with_weights_panics_on_length_mismatch() {
+        let _ = LightGroup::new(Vec::new()).with_weights(vec![1.0, 0.5]);
+    }
+}
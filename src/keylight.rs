@@ -1,3 +1,5 @@
+use crate::transition::Easing;
+use rand::Rng;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -30,6 +32,59 @@ pub enum ElgatoError {
 
     #[error(transparent)]
     CancelError(#[from] std::sync::mpsc::SendError<bool>),
+
+    #[error("Timeout")]
+    Timeout,
+}
+
+/// Tuning knobs for the retry/backoff behaviour applied to every request a `KeyLight` makes.
+///
+/// Each request is wrapped in a [`tokio::time::timeout`] of `request_timeout`. On failure it
+/// is retried up to `max_retries` times, waiting `base_delay * 2^attempt` (capped at
+/// `max_delay`) plus random jitter in `[0, delay/2)` between attempts.
+#[derive(Debug, Clone)]
+pub struct KeyLightConfig {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub request_timeout: Duration,
+}
+
+impl Default for KeyLightConfig {
+    fn default() -> Self {
+        KeyLightConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl KeyLightConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -47,17 +102,131 @@ pub struct Light {
     pub temperature: u16,
 }
 
+/// Convert a device-units temperature (143-344) back to Kelvin (2900-7000), the inverse of the
+/// mapping applied in [`KeyLight::set_temperature`].
+pub(crate) fn device_to_kelvin(device: u16) -> u32 {
+    (((device as f32).clamp(143.0, 344.0) - 143.0) * (4100.0 / (344.0 - 143.0)) + 2900.0) as u32
+}
+
+/// Convert Kelvin (2900-7000) to device units (143-344), the inverse of [`device_to_kelvin`].
+///
+/// Light expects a value between 143 to 344 where 143 is 2900K and 344 is 7000K.
+/// Figured this out by sniffing the official application. Might be different for other lights?
+pub(crate) fn kelvin_to_device(kelvin: u32) -> u16 {
+    (((kelvin as f32).clamp(2900.0, 7000.0) - 2900.0) / (4100.0 / (344.0 - 143.0)) + 143.0)
+        .clamp(143.0, 344.0) as u16
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct KeyLight {
     addr: Ipv4Addr,
+    port: u16,
     url: String,
     name: String,
 
     poll: bool,
     poll_cancel: tokio::sync::mpsc::Sender<bool>,
+    poll_task: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    // Separate slots per attribute: a brightness fade and a temperature fade are independent
+    // and must not cancel each other when run concurrently.
+    brightness_transition_cancel: Mutex<Option<mpsc::Sender<()>>>,
+    temperature_transition_cancel: Mutex<Option<mpsc::Sender<()>>>,
     client: reqwest::Client,
     status: Arc<Mutex<Status>>,
+    config: KeyLightConfig,
+}
+
+/// How often a fade samples a new value and sends it to the light.
+const TRANSITION_HZ: u64 = 25;
+
+/// Timeout used to probe a cached address in [`KeyLight::new_from_name`] before committing to
+/// it, independent of the caller's own `request_timeout`.
+const CACHE_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Drive one fade: tick at [`TRANSITION_HZ`], map elapsed time through `easing`, and apply
+/// `sample` to a fresh copy of the cached status each tick. Stops early if `cancel` fires.
+async fn run_transition(
+    url: String,
+    client: Client,
+    status: Arc<Mutex<Status>>,
+    config: KeyLightConfig,
+    mut cancel: mpsc::Receiver<()>,
+    duration: Duration,
+    easing: Easing,
+    sample: impl Fn(&mut Status, f64),
+) -> Result<(), ElgatoError> {
+    let mut interval = tokio::time::interval(Duration::from_millis(1000 / TRANSITION_HZ));
+    let started = tokio::time::Instant::now();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let progress = (started.elapsed().as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0);
+
+                let eased = easing.apply(progress);
+                let mut current = status.lock().await.clone();
+                sample(&mut current, eased);
+
+                send_with_retry(&config, || client.put(&url).json(&current)).await?;
+
+                // Re-sample onto whatever the cache holds now rather than blindly replacing it
+                // with `current`: another axis's fade (or `poll_status`) may have updated a
+                // different field while the PUT above was in flight, and blind replacement
+                // would stomp that update.
+                let mut lock = status.lock().await;
+                let mut merged = lock.clone();
+                sample(&mut merged, eased);
+                *lock.deref_mut() = merged;
+
+                if progress >= 1.0 {
+                    return Ok(());
+                }
+            }
+
+            _ = cancel.recv() => {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Send a request built from scratch on every attempt, retrying with exponential backoff and
+/// jitter until it succeeds or `config.max_retries` is exhausted. Each attempt is bounded by
+/// `config.request_timeout`.
+async fn send_with_retry(
+    config: &KeyLightConfig,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, ElgatoError> {
+    let mut attempt = 0;
+
+    loop {
+        let outcome = tokio::time::timeout(config.request_timeout, build().send()).await;
+
+        let err = match outcome {
+            Ok(Ok(resp)) => return Ok(resp),
+            Ok(Err(err)) => ElgatoError::from(err),
+            Err(_) => ElgatoError::Timeout,
+        };
+
+        if attempt >= config.max_retries {
+            return Err(err);
+        }
+
+        let delay = backoff_delay(config.base_delay, config.max_delay, attempt);
+        let jitter = Duration::from_secs_f64(rand::thread_rng().gen_range(0.0..0.5) * delay.as_secs_f64());
+
+        tokio::time::sleep(delay + jitter).await;
+        attempt += 1;
+    }
+}
+
+/// The exponential backoff delay for `attempt`, before jitter: `base_delay * 2^attempt`,
+/// capped at `max_delay`. Saturates rather than overflowing once `1 << attempt` would.
+fn backoff_delay(base_delay: Duration, max_delay: Duration, attempt: u32) -> Duration {
+    base_delay
+        .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+        .min(max_delay)
 }
 
 impl KeyLight {
@@ -67,12 +236,14 @@ impl KeyLight {
     ///
     /// * `addr` - IP address to the keylight
     /// * `poll` - If the library should poll the light for updates
+    /// * `config` - retry/backoff/timeout behaviour for requests made to this light
     pub async fn new_from_ip(
         name: &str,
         addr: Ipv4Addr,
         poll: bool,
+        config: KeyLightConfig,
     ) -> Result<KeyLight, ElgatoError> {
-        Ok(KeyLight::create(name, addr, 9123, poll).await?)
+        Ok(KeyLight::create(name, addr, 9123, poll, config).await?)
     }
 
     /// Create a new Keylight from device name
@@ -82,7 +253,36 @@ impl KeyLight {
     ///
     /// * `name` - Name of the lamp like "Key Light Left" or whatever your light is named
     /// * `poll` - If the library should poll the light for updates
-    pub async fn new_from_name(name: &str, poll: bool) -> Result<KeyLight, ElgatoError> {
+    /// * `config` - retry/backoff/timeout behaviour for requests made to this light
+    ///
+    /// Consults the on-disk device cache first and connects directly by the cached IP if it
+    /// still responds, only falling back to a fresh mDNS scan otherwise.
+    pub async fn new_from_name(
+        name: &str,
+        poll: bool,
+        config: KeyLightConfig,
+    ) -> Result<KeyLight, ElgatoError> {
+        if let Some(cached) = crate::cache::DeviceCache::get(name).await {
+            // Probe with a short, fixed budget instead of the caller's own retry/backoff
+            // config: a stale cache entry would otherwise burn the full retry budget (tens of
+            // seconds with the defaults) before falling back to a fresh scan, which is slower
+            // than just scanning in the first place.
+            let probe_config = KeyLightConfig::new()
+                .max_retries(0)
+                .request_timeout(CACHE_PROBE_TIMEOUT);
+            let probe_url = format!("http://{}:{}/elgato/lights", cached.addr, cached.port);
+            let probed =
+                send_with_retry(&probe_config, || reqwest::Client::new().get(&probe_url)).await;
+
+            if probed.is_ok() {
+                if let Ok(kl) =
+                    KeyLight::create(name, cached.addr, cached.port, poll, config.clone()).await
+                {
+                    return Ok(kl);
+                }
+            }
+        }
+
         let (tx, mut rx) = mpsc::channel(200);
         let (ctx, crx) = std::sync::mpsc::channel();
 
@@ -122,7 +322,112 @@ impl KeyLight {
 
         let addr = Ipv4Addr::from_str(&m.address())?;
 
-        Ok(KeyLight::create(m.name(), addr, *m.port(), poll).await?)
+        let kl = KeyLight::create(m.name(), addr, *m.port(), poll, config).await?;
+
+        crate::cache::DeviceCache::upsert(&kl.name, kl.addr, kl.port).await;
+
+        Ok(kl)
+    }
+
+    /// Discover every Elgato light on the network that responds within `timeout`.
+    ///
+    /// Unlike [`KeyLight::new_from_name`], which stops at the first responder matching a
+    /// given name, this collects every `_elg._tcp` service seen during the timeout window
+    /// and constructs a `KeyLight` for each one.
+    ///
+    /// A light that fails to parse its address or fails the initial health-check (e.g. it
+    /// went offline between mDNS discovery and connect) is skipped rather than failing the
+    /// whole call, so one flaky device can't take down every other already-connected light.
+    /// The failure is logged to stderr.
+    ///
+    /// Every successfully created light is recorded in the on-disk device cache, same as
+    /// [`KeyLight::new_from_name`], so a later `new_from_name` for any of them can skip
+    /// straight to the cached IP.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - how long to listen for mDNS responses before returning
+    /// * `poll` - if the constructed lights should poll for updates
+    /// * `config` - retry/backoff/timeout behaviour for requests made to each light
+    pub async fn discover_all(
+        timeout: Duration,
+        poll: bool,
+        config: KeyLightConfig,
+    ) -> Result<Vec<KeyLight>, ElgatoError> {
+        let (tx, mut rx) = mpsc::channel(200);
+        let (ctx, crx) = std::sync::mpsc::channel();
+
+        tokio::task::spawn_blocking(move || {
+            let mut browser = MdnsBrowser::new(ServiceType::new("elg", "tcp").unwrap());
+
+            browser.set_service_discovered_callback(Box::new(
+                move |result: zeroconf::Result<ServiceDiscovery>,
+                      _context: Option<Arc<dyn Any>>| {
+                    if let Ok(res) = result {
+                        let _ = tx.blocking_send(res);
+                    }
+                },
+            ));
+
+            let event_loop = browser.browse_services().unwrap();
+
+            loop {
+                event_loop.poll(Duration::from_millis(500)).unwrap();
+
+                match crx.try_recv() {
+                    Ok(_) => return,
+                    Err(e) => match e {
+                        std::sync::mpsc::TryRecvError::Empty => {}
+                        std::sync::mpsc::TryRecvError::Disconnected => return,
+                    },
+                }
+            }
+        });
+
+        let mut discovered = Vec::new();
+        let _ = tokio::time::timeout(timeout, async {
+            while let Some(m) = rx.recv().await {
+                discovered.push(m);
+            }
+        })
+        .await;
+
+        let _ = ctx.send(true);
+
+        let mut seen = std::collections::HashSet::new();
+        discovered.retain(|m| seen.insert((m.address().clone(), *m.port())));
+
+        let mut lights = Vec::new();
+        for m in discovered {
+            let addr = match Ipv4Addr::from_str(&m.address()) {
+                Ok(addr) => addr,
+                Err(err) => {
+                    eprintln!(
+                        "elgato-keylight-rs: skipping {}, failed to parse address {}: {}",
+                        m.name(),
+                        m.address(),
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            match KeyLight::create(m.name(), addr, *m.port(), poll, config.clone()).await {
+                Ok(kl) => {
+                    crate::cache::DeviceCache::upsert(&kl.name, kl.addr, kl.port).await;
+                    lights.push(kl);
+                }
+                Err(err) => {
+                    eprintln!(
+                        "elgato-keylight-rs: skipping {}, failed to connect: {}",
+                        m.name(),
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(lights)
     }
 
     async fn create(
@@ -130,18 +435,24 @@ impl KeyLight {
         ip: Ipv4Addr,
         port: u16,
         poll: bool,
+        config: KeyLightConfig,
     ) -> Result<KeyLight, ElgatoError> {
         let (ptx, ctx) = tokio::sync::mpsc::channel(5);
 
         let k = KeyLight {
             addr: ip,
+            port,
             url: format!("http://{}:{}/elgato/lights", ip.to_string(), port),
             name: name.to_string(),
 
             poll,
             poll_cancel: ptx,
+            poll_task: Mutex::new(None),
+            brightness_transition_cancel: Mutex::new(None),
+            temperature_transition_cancel: Mutex::new(None),
             client: reqwest::Client::new(),
             status: Default::default(),
+            config,
         };
 
         //Test the light
@@ -149,12 +460,14 @@ impl KeyLight {
         *k.status.lock().await.deref_mut() = s;
 
         if poll {
-            tokio::spawn(KeyLight::poll_status(
+            let handle = tokio::spawn(KeyLight::poll_status(
                 k.url.clone(),
                 k.client.clone(),
                 k.status.clone(),
+                k.config.clone(),
                 ctx,
             ));
+            *k.poll_task.lock().await = Some(handle);
         }
 
         Ok(k)
@@ -164,21 +477,37 @@ impl KeyLight {
         url: String,
         client: Client,
         cache: Arc<Mutex<Status>>,
+        config: KeyLightConfig,
         mut cancel: tokio::sync::mpsc::Receiver<bool>,
     ) {
         let mut interval = tokio::time::interval(Duration::from_secs(5));
         loop {
             tokio::select! {
                 _ = interval.tick() =>  {
-                    match client.get(&url).send().await {
-                        Ok(data) => match data.json::<Status>().await {
-                            Ok(status) => {
-                                *cache.lock().await.deref_mut() = status;
-                            }
-                            Err(_) => {}
-                        },
-                        Err(_) => {}
-                    };
+                    // Race the retry/backoff sequence against cancellation too, so `stop()`
+                    // doesn't have to wait out a full retry budget (tens of seconds with the
+                    // defaults) on a dead endpoint before it can return.
+                    tokio::select! {
+                        resp = send_with_retry(&config, || client.get(&url)) => {
+                            match resp {
+                                Ok(data) => match data.json::<Status>().await {
+                                    Ok(status) => {
+                                        *cache.lock().await.deref_mut() = status;
+                                    }
+                                    Err(err) => {
+                                        eprintln!("elgato-keylight-rs: failed to parse status from {}: {}", url, err);
+                                    }
+                                },
+                                Err(err) => {
+                                    eprintln!("elgato-keylight-rs: failed to poll {}: {}", url, err);
+                                }
+                            };
+                        }
+
+                        _ = cancel.recv() => {
+                            return;
+                        }
+                    }
                 }
 
                 _ = cancel.recv() => {
@@ -191,7 +520,7 @@ impl KeyLight {
 
     /// Get the current settings of the light, if polling is enabled, returns the cached data.
     async fn get_status(&self) -> Result<Status, ElgatoError> {
-        let resp = self.client.get(&self.url).send().await?;
+        let resp = send_with_retry(&self.config, || self.client.get(&self.url)).await?;
 
         Ok(resp.json::<Status>().await?)
     }
@@ -218,15 +547,14 @@ impl KeyLight {
             brightness = 100;
         }
 
-        let mut lock = self.status.lock().await;
-        let mut current = lock.clone();
+        let mut current = self.status.lock().await.clone();
         for i in current.lights.iter_mut() {
             i.brightness = brightness;
         }
 
-        self.client.put(&self.url).json(&current).send().await?;
+        send_with_retry(&self.config, || self.client.put(&self.url).json(&current)).await?;
 
-        *lock.deref_mut() = current;
+        *self.status.lock().await.deref_mut() = current;
 
         Ok(())
     }
@@ -244,8 +572,7 @@ impl KeyLight {
             brightness = 1.0;
         }
 
-        let mut lock = self.status.lock().await;
-        let mut current = lock.clone();
+        let mut current = self.status.lock().await.clone();
 
         let mut avg = Vec::new();
         for i in current.lights.iter_mut() {
@@ -254,9 +581,9 @@ impl KeyLight {
             avg.push(nv);
         }
 
-        self.client.put(&self.url).json(&current).send().await?;
+        send_with_retry(&self.config, || self.client.put(&self.url).json(&current)).await?;
 
-        *lock.deref_mut() = current;
+        *self.status.lock().await.deref_mut() = current;
 
         Ok(avg.iter().sum::<f64>() / avg.len() as f64)
     }
@@ -267,23 +594,16 @@ impl KeyLight {
     ///
     /// * `temperature` - Value between 2900 - 7000 (Kelvin)
     pub async fn set_temperature(&mut self, temperature: u32) -> Result<(), ElgatoError> {
-        // Light expects a value between 143 to 344 where 143 is 2900K and 344 is 7000K.
-        // Figured this out by sniffing the official application.
-        // Might be different for other lights?
-        let temperature = (((temperature as f32).clamp(2900.0, 7000.0) - 2900.0)
-            / (4100.0 / (344.0 - 143.0))
-            + 143.0)
-            .clamp(143.0, 344.0) as u16;
+        let temperature = kelvin_to_device(temperature);
 
-        let mut lock = self.status.lock().await;
-        let mut current = lock.clone();
+        let mut current = self.status.lock().await.clone();
         for i in current.lights.iter_mut() {
             i.temperature = temperature;
         }
 
-        self.client.put(&self.url).json(&current).send().await?;
+        send_with_retry(&self.config, || self.client.put(&self.url).json(&current)).await?;
 
-        *lock.deref_mut() = current;
+        *self.status.lock().await.deref_mut() = current;
 
         Ok(())
     }
@@ -297,16 +617,198 @@ impl KeyLight {
         // Figured this out by using the official application.
         // Might be different for other lights?
 
-        let mut lock = self.status.lock().await;
-        let mut current = lock.clone();
+        let mut current = self.status.lock().await.clone();
         for i in current.lights.iter_mut() {
             i.on = power as u8;
         }
 
-        self.client.put(&self.url).json(&current).send().await?;
+        send_with_retry(&self.config, || self.client.put(&self.url).json(&current)).await?;
 
-        *lock.deref_mut() = current;
+        *self.status.lock().await.deref_mut() = current;
 
         Ok(())
     }
+
+    /// Cancel any brightness fade already running on this light, if one is in flight.
+    async fn cancel_brightness_transition(&self) {
+        if let Some(cancel) = self.brightness_transition_cancel.lock().await.take() {
+            let _ = cancel.send(()).await;
+        }
+    }
+
+    /// Cancel any temperature fade already running on this light, if one is in flight.
+    async fn cancel_temperature_transition(&self) {
+        if let Some(cancel) = self.temperature_transition_cancel.lock().await.take() {
+            let _ = cancel.send(()).await;
+        }
+    }
+
+    /// Smoothly fade the light's brightness to `target` over `duration`, sampling at
+    /// [`TRANSITION_HZ`]. Cancels any brightness fade already running on this light; a
+    /// concurrent [`KeyLight::fade_temperature`] is unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Value between 0-100
+    /// * `duration` - how long the fade should take
+    /// * `easing` - the curve to interpolate with
+    pub async fn fade_brightness(
+        &self,
+        target: u8,
+        duration: Duration,
+        easing: Easing,
+    ) -> Result<(), ElgatoError> {
+        self.cancel_brightness_transition().await;
+
+        let start = self
+            .status
+            .lock()
+            .await
+            .lights
+            .first()
+            .map(|l| l.brightness as f64)
+            .unwrap_or(0.0);
+        let target = target.min(100) as f64;
+
+        let (tx, rx) = mpsc::channel(1);
+        *self.brightness_transition_cancel.lock().await = Some(tx);
+
+        run_transition(
+            self.url.clone(),
+            self.client.clone(),
+            self.status.clone(),
+            self.config.clone(),
+            rx,
+            duration,
+            easing,
+            move |status, t| {
+                let value = (start + (target - start) * t).clamp(0.0, 100.0) as u8;
+                for light in status.lights.iter_mut() {
+                    light.brightness = value;
+                }
+            },
+        )
+        .await
+    }
+
+    /// Smoothly fade the light's color temperature to `target` Kelvin over `duration`,
+    /// sampling at [`TRANSITION_HZ`]. Cancels any temperature fade already running on this
+    /// light; a concurrent [`KeyLight::fade_brightness`] is unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Value between 2900 - 7000 (Kelvin)
+    /// * `duration` - how long the fade should take
+    /// * `easing` - the curve to interpolate with
+    pub async fn fade_temperature(
+        &self,
+        target: u32,
+        duration: Duration,
+        easing: Easing,
+    ) -> Result<(), ElgatoError> {
+        self.cancel_temperature_transition().await;
+
+        let start = self
+            .status
+            .lock()
+            .await
+            .lights
+            .first()
+            .map(|l| device_to_kelvin(l.temperature) as f64)
+            .unwrap_or(2900.0);
+        let target = target.clamp(2900, 7000) as f64;
+
+        let (tx, rx) = mpsc::channel(1);
+        *self.temperature_transition_cancel.lock().await = Some(tx);
+
+        run_transition(
+            self.url.clone(),
+            self.client.clone(),
+            self.status.clone(),
+            self.config.clone(),
+            rx,
+            duration,
+            easing,
+            move |status, t| {
+                let kelvin = (start + (target - start) * t) as u32;
+                let device = kelvin_to_device(kelvin);
+                for light in status.lights.iter_mut() {
+                    light.temperature = device;
+                }
+            },
+        )
+        .await
+    }
+
+    /// Stop the background polling task and wait for it to finish.
+    ///
+    /// Safe to call even if `poll` was never enabled, or if `stop` has already been called.
+    pub async fn stop(&self) {
+        let _ = self.poll_cancel.send(true).await;
+
+        if let Some(handle) = self.poll_task.lock().await.take() {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Drop for KeyLight {
+    fn drop(&mut self) {
+        // Best-effort: we can't await the poll task from `drop`, so just ask it to stop.
+        // Call `stop()` explicitly if you need to wait for the task to actually exit.
+        let _ = self.poll_cancel.try_send(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kelvin_to_device_clamps_to_valid_range() {
+        assert_eq!(kelvin_to_device(0), 143);
+        assert_eq!(kelvin_to_device(2900), 143);
+        assert_eq!(kelvin_to_device(7000), 344);
+        assert_eq!(kelvin_to_device(10_000), 344);
+    }
+
+    #[test]
+    fn device_to_kelvin_clamps_to_valid_range() {
+        assert_eq!(device_to_kelvin(0), 2900);
+        assert_eq!(device_to_kelvin(143), 2900);
+        assert_eq!(device_to_kelvin(344), 7000);
+        assert_eq!(device_to_kelvin(1000), 7000);
+    }
+
+    #[test]
+    fn kelvin_device_round_trips_within_rounding_error() {
+        for kelvin in (2900..=7000).step_by(137) {
+            let device = kelvin_to_device(kelvin);
+            let back = device_to_kelvin(device);
+            assert!(
+                (back as i64 - kelvin as i64).abs() <= 20,
+                "kelvin {kelvin} round-tripped to {back} via device unit {device}"
+            );
+        }
+    }
+
+    #[test]
+    fn backoff_delay_doubles_each_attempt_until_capped() {
+        let base = Duration::from_millis(200);
+        let max = Duration::from_secs(5);
+
+        assert_eq!(backoff_delay(base, max, 0), Duration::from_millis(200));
+        assert_eq!(backoff_delay(base, max, 1), Duration::from_millis(400));
+        assert_eq!(backoff_delay(base, max, 2), Duration::from_millis(800));
+        assert_eq!(backoff_delay(base, max, 5), max);
+    }
+
+    #[test]
+    fn backoff_delay_does_not_overflow_for_large_attempts() {
+        let base = Duration::from_millis(200);
+        let max = Duration::from_secs(5);
+
+        assert_eq!(backoff_delay(base, max, 32), max);
+        assert_eq!(backoff_delay(base, max, u32::MAX), max);
+    }
 }
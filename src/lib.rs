@@ -0,0 +1,14 @@
+mod cache;
+pub mod group;
+pub mod keylight;
+pub mod transition;
+
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+
+pub use group::LightGroup;
+pub use keylight::{ElgatoError, KeyLight, KeyLightConfig, Light, Status};
+pub use transition::Easing;
+
+#[cfg(feature = "mqtt")]
+pub use mqtt::{MqttBridge, MqttError};
@@ -0,0 +1,305 @@
+//! MQTT bridge with Home Assistant auto-discovery.
+//!
+//! Enabled by the `mqtt` feature. A [`MqttBridge`] hands one or more [`KeyLight`]s to an MQTT
+//! broker: it subscribes to a command topic per light to drive `set_power`/`set_brightness`/
+//! `set_temperature`, and publishes the cached [`Status`] to a state topic whenever it changes.
+//! On connect it also publishes a Home Assistant MQTT-discovery config for each light so it
+//! shows up automatically as a `light` entity with brightness and color-temperature support.
+
+use crate::keylight::{KeyLight, Status};
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, Publish, QoS};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+#[derive(Error, Debug)]
+pub enum MqttError {
+    #[error(transparent)]
+    ClientError(#[from] rumqttc::ClientError),
+
+    #[error(transparent)]
+    ElgatoError(#[from] crate::keylight::ElgatoError),
+
+    #[error("malformed command payload")]
+    BadCommand,
+}
+
+/// Payload accepted on a light's command topic. Any field left unset is left unchanged.
+#[derive(Debug, Deserialize)]
+struct Command {
+    state: Option<String>,
+    brightness: Option<u8>,
+    color_temp_kelvin: Option<u32>,
+}
+
+/// Lowercase `name`, replacing every inner run of non-alphanumeric characters with a single
+/// underscore and dropping leading and trailing ones, for use in a stable, MQTT-topic-safe
+/// slug. A name with no alphanumeric characters becomes `"keylight"`.
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    let mut last_was_sep = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+            last_was_sep = false;
+        } else if !last_was_sep {
+            slug.push('_');
+            last_was_sep = true;
+        }
+    }
+    let slug = slug.trim_matches('_').to_string();
+    if slug.is_empty() {
+        "keylight".to_string()
+    } else {
+        slug
+    }
+}
+
+struct BridgedLight {
+    /// Used to build this light's topic names; must be unique and MQTT-topic-safe.
+    slug: String,
+    light: Arc<Mutex<KeyLight>>,
+    last_published: Mutex<Option<Status>>,
+}
+
+/// Bridges a set of [`KeyLight`]s to an MQTT broker, with Home Assistant auto-discovery.
+pub struct MqttBridge {
+    client: AsyncClient,
+    eventloop: EventLoop,
+    lights: Vec<BridgedLight>,
+    discovery_prefix: String,
+}
+
+impl MqttBridge {
+    /// Connect to `broker_host:broker_port` and prepare to bridge `lights`.
+    ///
+    /// `discovery_prefix` is the Home Assistant MQTT-discovery topic prefix, usually
+    /// `"homeassistant"`.
+    ///
+    /// Each light's slug (used as its `unique_id` and in its topic names) is derived from its
+    /// device name rather than its position in `lights`, so a restart that discovers the same
+    /// lights in a different order doesn't reassign Home Assistant's entity registry to the
+    /// wrong physical light. Name collisions get a numeric suffix.
+    pub async fn new(
+        client_id: &str,
+        broker_host: &str,
+        broker_port: u16,
+        discovery_prefix: &str,
+        lights: Vec<KeyLight>,
+    ) -> Self {
+        let mut options = MqttOptions::new(client_id, broker_host, broker_port);
+        options.set_keep_alive(Duration::from_secs(30));
+
+        let (client, eventloop) = AsyncClient::new(options, 50);
+
+        let mut seen = std::collections::HashMap::new();
+        let mut bridged_lights = Vec::with_capacity(lights.len());
+        for light in lights {
+            let slug = slugify(&light.name().await);
+            let count = seen.entry(slug.clone()).or_insert(0usize);
+            *count += 1;
+            let slug = if *count > 1 {
+                format!("{}_{}", slug, count)
+            } else {
+                slug
+            };
+
+            bridged_lights.push(BridgedLight {
+                slug,
+                light: Arc::new(Mutex::new(light)),
+                last_published: Mutex::new(None),
+            });
+        }
+
+        MqttBridge {
+            client,
+            eventloop,
+            lights: bridged_lights,
+            discovery_prefix: discovery_prefix.to_string(),
+        }
+    }
+
+    fn command_topic(&self, slug: &str) -> String {
+        format!("elgato_keylight/{}/set", slug)
+    }
+
+    fn state_topic(&self, slug: &str) -> String {
+        format!("elgato_keylight/{}/state", slug)
+    }
+
+    fn discovery_topic(&self, slug: &str) -> String {
+        format!("{}/light/{}/config", self.discovery_prefix, slug)
+    }
+
+    /// Publish the Home Assistant discovery config and subscribe to the command topic for
+    /// every bridged light.
+    async fn announce(&self) -> Result<(), MqttError> {
+        for bridged in &self.lights {
+            let name = bridged.light.lock().await.name().await;
+
+            let config = json!({
+                "name": name,
+                "unique_id": bridged.slug,
+                "schema": "json",
+                "state_topic": self.state_topic(&bridged.slug),
+                "command_topic": self.command_topic(&bridged.slug),
+                "brightness": true,
+                "brightness_scale": 100,
+                "color_mode": true,
+                "supported_color_modes": ["color_temp"],
+                "color_temp_kelvin": true,
+                "min_kelvin": 2900,
+                "max_kelvin": 7000,
+            });
+
+            self.client
+                .publish(
+                    self.discovery_topic(&bridged.slug),
+                    QoS::AtLeastOnce,
+                    true,
+                    config.to_string(),
+                )
+                .await?;
+
+            self.client
+                .subscribe(self.command_topic(&bridged.slug), QoS::AtLeastOnce)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Publish the cached status of `bridged` if it changed since the last publish.
+    async fn publish_light_state(&self, bridged: &BridgedLight) -> Result<(), MqttError> {
+        let status = bridged.light.lock().await.get().await?;
+
+        let mut last = bridged.last_published.lock().await;
+        if last.as_ref() == Some(&status) {
+            return Ok(());
+        }
+
+        let light = status.lights.first();
+        let payload = json!({
+            "state": if light.map(|l| l.on != 0).unwrap_or(false) { "ON" } else { "OFF" },
+            "brightness": light.map(|l| l.brightness).unwrap_or(0),
+            "color_mode": "color_temp",
+            "color_temp_kelvin": light
+                .map(|l| crate::keylight::device_to_kelvin(l.temperature))
+                .unwrap_or(2900),
+        });
+
+        self.client
+            .publish(
+                self.state_topic(&bridged.slug),
+                QoS::AtLeastOnce,
+                true,
+                payload.to_string(),
+            )
+            .await?;
+
+        *last = Some(status);
+
+        Ok(())
+    }
+
+    /// Publish the cached status of every light that changed since the last publish.
+    ///
+    /// A light that fails to report its status (e.g. it's exhausted its retries) is logged and
+    /// skipped rather than aborting the rest of the lights for this tick.
+    async fn publish_state(&self) {
+        for bridged in &self.lights {
+            if let Err(err) = self.publish_light_state(bridged).await {
+                eprintln!(
+                    "elgato-keylight-rs: failed to publish state for {}: {}",
+                    bridged.slug, err
+                );
+            }
+        }
+    }
+
+    async fn handle_command(&self, publish: Publish) -> Result<(), MqttError> {
+        let bridged = self
+            .lights
+            .iter()
+            .find(|b| publish.topic == self.command_topic(&b.slug));
+
+        let Some(bridged) = bridged else {
+            return Ok(());
+        };
+
+        let command: Command =
+            serde_json::from_slice(&publish.payload).map_err(|_| MqttError::BadCommand)?;
+
+        let mut light = bridged.light.lock().await;
+
+        if let Some(state) = command.state {
+            light.set_power(state.eq_ignore_ascii_case("on")).await?;
+        }
+
+        if let Some(brightness) = command.brightness {
+            light.set_brightness(brightness).await?;
+        }
+
+        if let Some(kelvin) = command.color_temp_kelvin {
+            light.set_temperature(kelvin).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Run the bridge: announce discovery configs once, then service incoming commands and
+    /// publish state changes. An error from the event loop (such as a lost connection) is not
+    /// returned; the loop keeps polling, so this only returns if the initial announce fails.
+    pub async fn run(mut self) -> Result<(), MqttError> {
+        self.announce().await?;
+
+        let mut state_tick = tokio::time::interval(Duration::from_secs(5));
+
+        loop {
+            tokio::select! {
+                event = self.eventloop.poll() => {
+                    if let Ok(Event::Incoming(Packet::Publish(publish))) = event {
+                        if let Err(err) = self.handle_command(publish).await {
+                            eprintln!("elgato-keylight-rs: mqtt command failed: {}", err);
+                        }
+                    }
+                }
+
+                _ = state_tick.tick() => {
+                    self.publish_state().await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_lowercases_and_collapses_separators() {
+        assert_eq!(slugify("Key Light Left"), "key_light_left");
+        assert_eq!(slugify("Back---Light!!"), "back_light");
+    }
+
+    #[test]
+    fn slugify_trims_leading_and_trailing_separators() {
+        assert_eq!(slugify("  Key Light  "), "key_light");
+        assert_eq!(slugify("___Key Light___"), "key_light");
+    }
+
+    #[test]
+    fn slugify_falls_back_when_nothing_alphanumeric_survives() {
+        assert_eq!(slugify(""), "keylight");
+        assert_eq!(slugify("!!!"), "keylight");
+    }
+
+    #[test]
+    fn slugify_is_stable_for_already_valid_slugs() {
+        assert_eq!(slugify("key_light_left"), "key_light_left");
+    }
+}
@@ -0,0 +1,81 @@
+//! Easing curves used to interpolate `KeyLight` transitions.
+
+/// An easing curve used to interpolate a
+/// [`KeyLight`](crate::keylight::KeyLight) transition between its current value and a target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseInCubic,
+    EaseOutCubic,
+    EaseInOutCubic,
+    Sine,
+}
+
+impl Easing {
+    /// Map `t`, the progress through the transition, to the corresponding point on the curve.
+    /// `t` is clamped to `[0.0, 1.0]` and the result is always in `[0.0, 1.0]`.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInCubic => t * t * t,
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Sine => 1.0 - ((t * std::f64::consts::PI) / 2.0).cos(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [Easing; 5] = [
+        Easing::Linear,
+        Easing::EaseInCubic,
+        Easing::EaseOutCubic,
+        Easing::EaseInOutCubic,
+        Easing::Sine,
+    ];
+
+    #[test]
+    fn every_curve_starts_at_zero_and_ends_at_one() {
+        for curve in CURVES {
+            assert!((curve.apply(0.0) - 0.0).abs() < 1e-9, "{curve:?} at t=0");
+            assert!((curve.apply(1.0) - 1.0).abs() < 1e-9, "{curve:?} at t=1");
+        }
+    }
+
+    #[test]
+    fn every_curve_clamps_out_of_range_t() {
+        for curve in CURVES {
+            assert_eq!(curve.apply(-1.0), curve.apply(0.0));
+            assert_eq!(curve.apply(2.0), curve.apply(1.0));
+        }
+    }
+
+    #[test]
+    fn linear_is_identity() {
+        assert_eq!(Easing::Linear.apply(0.25), 0.25);
+        assert_eq!(Easing::Linear.apply(0.75), 0.75);
+    }
+
+    #[test]
+    fn every_curve_is_monotonically_nondecreasing() {
+        for curve in CURVES {
+            let mut prev = curve.apply(0.0);
+            for i in 1..=100 {
+                let next = curve.apply(i as f64 / 100.0);
+                assert!(next >= prev - 1e-9, "{curve:?} decreased at step {i}");
+                prev = next;
+            }
+        }
+    }
+}